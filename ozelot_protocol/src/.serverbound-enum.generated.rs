@@ -3,7 +3,7 @@ Do not manually edit this file, if you wish to make
 changes here, then edit and rerun packets.clj */
 
 /// Represents a single packet
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum ServerboundPacket {
     Handshake(Handshake),
     StatusRequest(StatusRequest),
@@ -46,68 +46,73 @@ pub enum ServerboundPacket {
 
 }
 
-impl Packet for ServerboundPacket {
-    pub fn deserialize<R: Read>(r: &mut R, state: &ClientState) -> Result<Self> {
-        let packet_id = read_varint(r)?;
+impl ServerboundPacket {
+    /// Reads a packet off the wire. `protocol_version` selects which
+    /// wire-id <-> internal-id table to translate `packet_id` through, so a
+    /// single build of ozelot can speak to any protocol version listed in
+    /// `protocol_ids::SUPPORTED_PROTOCOLS`.
+    pub fn deserialize<R: Read>(r: &mut R, state: &ClientState, protocol_version: i32) -> Result<Self> {
+        let wire_id = read_varint(r)?;
+        let packet_id = protocol_ids::internal_id(state, protocol_version, wire_id)?;
         match state {
         &ClientState::Handshake => {
             match packet_id {
-            0 => Ok(Handshake::deserialize(r)?),
+            internal_ids::handshake::HANDSHAKE => Ok(ServerboundPacket::Handshake(Handshake::deserialize(r)?)),
 
             _ => bail!("No packet with id {} in state {}", packet_id, state),
             }
         },
         &ClientState::Status => {
             match packet_id {
-            0 => Ok(StatusRequest::deserialize(r)?),
-            1 => Ok(StatusPing::deserialize(r)?),
+            internal_ids::status::STATUS_REQUEST => Ok(ServerboundPacket::StatusRequest(StatusRequest::deserialize(r)?)),
+            internal_ids::status::STATUS_PING => Ok(ServerboundPacket::StatusPing(StatusPing::deserialize(r)?)),
 
             _ => bail!("No packet with id {} in state {}", packet_id, state),
             }
         },
         &ClientState::Login => {
             match packet_id {
-            0 => Ok(LoginStart::deserialize(r)?),
-            1 => Ok(EncryptionResponse::deserialize(r)?),
+            internal_ids::login::LOGIN_START => Ok(ServerboundPacket::LoginStart(LoginStart::deserialize(r)?)),
+            internal_ids::login::ENCRYPTION_RESPONSE => Ok(ServerboundPacket::EncryptionResponse(EncryptionResponse::deserialize(r)?)),
 
             _ => bail!("No packet with id {} in state {}", packet_id, state),
             }
         },
         &ClientState::Play => {
             match packet_id {
-            0 => Ok(TeleportConfirm::deserialize(r)?),
-            1 => Ok(TabComplete::deserialize(r)?),
-            2 => Ok(ChatMessage::deserialize(r)?),
-            3 => Ok(ClientStatus::deserialize(r)?),
-            4 => Ok(ClientSettings::deserialize(r)?),
-            5 => Ok(ConfirmTransaction::deserialize(r)?),
-            6 => Ok(EnchantItem::deserialize(r)?),
-            7 => Ok(ClickWindow::deserialize(r)?),
-            8 => Ok(CloseWindow::deserialize(r)?),
-            9 => Ok(PluginMessage::deserialize(r)?),
-            10 => Ok(UseEntity::deserialize(r)?),
-            11 => Ok(KeepAlive::deserialize(r)?),
-            12 => Ok(Player::deserialize(r)?),
-            13 => Ok(PlayerPosition::deserialize(r)?),
-            14 => Ok(PlayerPositionAndLook::deserialize(r)?),
-            15 => Ok(PlayerLook::deserialize(r)?),
-            16 => Ok(VehicleMove::deserialize(r)?),
-            17 => Ok(SteerBoat::deserialize(r)?),
-            18 => Ok(CraftRecipeRequest::deserialize(r)?),
-            19 => Ok(PlayerAbilities::deserialize(r)?),
-            20 => Ok(PlayerDigging::deserialize(r)?),
-            21 => Ok(EntityAction::deserialize(r)?),
-            22 => Ok(SteerVehicle::deserialize(r)?),
-            23 => Ok(CraftingBookData::deserialize(r)?),
-            24 => Ok(ResourcePackStatus::deserialize(r)?),
-            25 => Ok(AdvancementTab::deserialize(r)?),
-            26 => Ok(HeldItemChange::deserialize(r)?),
-            27 => Ok(CreativeInventoryAction::deserialize(r)?),
-            28 => Ok(UpdateSign::deserialize(r)?),
-            29 => Ok(Animation::deserialize(r)?),
-            30 => Ok(Spectate::deserialize(r)?),
-            31 => Ok(PlayerBlockPlacement::deserialize(r)?),
-            32 => Ok(UseItem::deserialize(r)?),
+            internal_ids::play::TELEPORT_CONFIRM => Ok(ServerboundPacket::TeleportConfirm(TeleportConfirm::deserialize(r)?)),
+            internal_ids::play::TAB_COMPLETE => Ok(ServerboundPacket::TabComplete(TabComplete::deserialize(r)?)),
+            internal_ids::play::CHAT_MESSAGE => Ok(ServerboundPacket::ChatMessage(ChatMessage::deserialize(r)?)),
+            internal_ids::play::CLIENT_STATUS => Ok(ServerboundPacket::ClientStatus(ClientStatus::deserialize(r)?)),
+            internal_ids::play::CLIENT_SETTINGS => Ok(ServerboundPacket::ClientSettings(ClientSettings::deserialize(r)?)),
+            internal_ids::play::CONFIRM_TRANSACTION => Ok(ServerboundPacket::ConfirmTransaction(ConfirmTransaction::deserialize(r)?)),
+            internal_ids::play::ENCHANT_ITEM => Ok(ServerboundPacket::EnchantItem(EnchantItem::deserialize(r)?)),
+            internal_ids::play::CLICK_WINDOW => Ok(ServerboundPacket::ClickWindow(ClickWindow::deserialize(r)?)),
+            internal_ids::play::CLOSE_WINDOW => Ok(ServerboundPacket::CloseWindow(CloseWindow::deserialize(r)?)),
+            internal_ids::play::PLUGIN_MESSAGE => Ok(ServerboundPacket::PluginMessage(PluginMessage::deserialize(r)?)),
+            internal_ids::play::USE_ENTITY => Ok(ServerboundPacket::UseEntity(UseEntity::deserialize(r)?)),
+            internal_ids::play::KEEP_ALIVE => Ok(ServerboundPacket::KeepAlive(KeepAlive::deserialize(r, protocol_version)?)),
+            internal_ids::play::PLAYER => Ok(ServerboundPacket::Player(Player::deserialize(r)?)),
+            internal_ids::play::PLAYER_POSITION => Ok(ServerboundPacket::PlayerPosition(PlayerPosition::deserialize(r)?)),
+            internal_ids::play::PLAYER_POSITION_AND_LOOK => Ok(ServerboundPacket::PlayerPositionAndLook(PlayerPositionAndLook::deserialize(r)?)),
+            internal_ids::play::PLAYER_LOOK => Ok(ServerboundPacket::PlayerLook(PlayerLook::deserialize(r)?)),
+            internal_ids::play::VEHICLE_MOVE => Ok(ServerboundPacket::VehicleMove(VehicleMove::deserialize(r)?)),
+            internal_ids::play::STEER_BOAT => Ok(ServerboundPacket::SteerBoat(SteerBoat::deserialize(r)?)),
+            internal_ids::play::CRAFT_RECIPE_REQUEST => Ok(ServerboundPacket::CraftRecipeRequest(CraftRecipeRequest::deserialize(r)?)),
+            internal_ids::play::PLAYER_ABILITIES => Ok(ServerboundPacket::PlayerAbilities(PlayerAbilities::deserialize(r)?)),
+            internal_ids::play::PLAYER_DIGGING => Ok(ServerboundPacket::PlayerDigging(PlayerDigging::deserialize(r)?)),
+            internal_ids::play::ENTITY_ACTION => Ok(ServerboundPacket::EntityAction(EntityAction::deserialize(r)?)),
+            internal_ids::play::STEER_VEHICLE => Ok(ServerboundPacket::SteerVehicle(SteerVehicle::deserialize(r)?)),
+            internal_ids::play::CRAFTING_BOOK_DATA => Ok(ServerboundPacket::CraftingBookData(CraftingBookData::deserialize(r)?)),
+            internal_ids::play::RESOURCE_PACK_STATUS => Ok(ServerboundPacket::ResourcePackStatus(ResourcePackStatus::deserialize(r)?)),
+            internal_ids::play::ADVANCEMENT_TAB => Ok(ServerboundPacket::AdvancementTab(AdvancementTab::deserialize(r)?)),
+            internal_ids::play::HELD_ITEM_CHANGE => Ok(ServerboundPacket::HeldItemChange(HeldItemChange::deserialize(r)?)),
+            internal_ids::play::CREATIVE_INVENTORY_ACTION => Ok(ServerboundPacket::CreativeInventoryAction(CreativeInventoryAction::deserialize(r)?)),
+            internal_ids::play::UPDATE_SIGN => Ok(ServerboundPacket::UpdateSign(UpdateSign::deserialize(r)?)),
+            internal_ids::play::ANIMATION => Ok(ServerboundPacket::Animation(Animation::deserialize(r)?)),
+            internal_ids::play::SPECTATE => Ok(ServerboundPacket::Spectate(Spectate::deserialize(r)?)),
+            internal_ids::play::PLAYER_BLOCK_PLACEMENT => Ok(ServerboundPacket::PlayerBlockPlacement(PlayerBlockPlacement::deserialize(r)?)),
+            internal_ids::play::USE_ITEM => Ok(ServerboundPacket::UseItem(UseItem::deserialize(r)?)),
 
             _ => bail!("No packet with id {} in state {}", packet_id, state),
             }
@@ -115,7 +120,10 @@ impl Packet for ServerboundPacket {
 
         }
     }
-    pub fn get_packet_name(&self) -> &str {
+}
+
+impl Packet for ServerboundPacket {
+    fn get_packet_name(&self) -> &str {
         match self {
         &ServerboundPacket::Handshake(..) => "Handshake",
         &ServerboundPacket::StatusRequest(..) => "StatusRequest",
@@ -158,7 +166,7 @@ impl Packet for ServerboundPacket {
 
         }
     }
-    pub fn get_clientstate(&self) -> ClientState {
+    fn get_clientstate(&self) -> ClientState {
         match self {
         &ServerboundPacket::Handshake(..) => ClientState::Handshake,
         &ServerboundPacket::StatusRequest(..) => ClientState::Status,
@@ -201,50 +209,63 @@ impl Packet for ServerboundPacket {
 
         }
     }
-    pub fn get_id(&self) -> i32 {
+}
+
+impl ServerboundPacket {
+    /// Internal, protocol-version-independent id for this variant. This is
+    /// what `get_id` used to return before ozelot supported more than one
+    /// protocol version; it's still used as the index into the
+    /// `protocol_ids` translation tables.
+    fn get_internal_id(&self) -> i32 {
         match self {
-        &ServerboundPacket::Handshake(..) => 0,
-        &ServerboundPacket::StatusRequest(..) => 0,
-        &ServerboundPacket::StatusPing(..) => 1,
-        &ServerboundPacket::LoginStart(..) => 0,
-        &ServerboundPacket::EncryptionResponse(..) => 1,
-        &ServerboundPacket::TeleportConfirm(..) => 0,
-        &ServerboundPacket::TabComplete(..) => 1,
-        &ServerboundPacket::ChatMessage(..) => 2,
-        &ServerboundPacket::ClientStatus(..) => 3,
-        &ServerboundPacket::ClientSettings(..) => 4,
-        &ServerboundPacket::ConfirmTransaction(..) => 5,
-        &ServerboundPacket::EnchantItem(..) => 6,
-        &ServerboundPacket::ClickWindow(..) => 7,
-        &ServerboundPacket::CloseWindow(..) => 8,
-        &ServerboundPacket::PluginMessage(..) => 9,
-        &ServerboundPacket::UseEntity(..) => 10,
-        &ServerboundPacket::KeepAlive(..) => 11,
-        &ServerboundPacket::Player(..) => 12,
-        &ServerboundPacket::PlayerPosition(..) => 13,
-        &ServerboundPacket::PlayerPositionAndLook(..) => 14,
-        &ServerboundPacket::PlayerLook(..) => 15,
-        &ServerboundPacket::VehicleMove(..) => 16,
-        &ServerboundPacket::SteerBoat(..) => 17,
-        &ServerboundPacket::CraftRecipeRequest(..) => 18,
-        &ServerboundPacket::PlayerAbilities(..) => 19,
-        &ServerboundPacket::PlayerDigging(..) => 20,
-        &ServerboundPacket::EntityAction(..) => 21,
-        &ServerboundPacket::SteerVehicle(..) => 22,
-        &ServerboundPacket::CraftingBookData(..) => 23,
-        &ServerboundPacket::ResourcePackStatus(..) => 24,
-        &ServerboundPacket::AdvancementTab(..) => 25,
-        &ServerboundPacket::HeldItemChange(..) => 26,
-        &ServerboundPacket::CreativeInventoryAction(..) => 27,
-        &ServerboundPacket::UpdateSign(..) => 28,
-        &ServerboundPacket::Animation(..) => 29,
-        &ServerboundPacket::Spectate(..) => 30,
-        &ServerboundPacket::PlayerBlockPlacement(..) => 31,
-        &ServerboundPacket::UseItem(..) => 32,
+        &ServerboundPacket::Handshake(..) => internal_ids::handshake::HANDSHAKE,
+        &ServerboundPacket::StatusRequest(..) => internal_ids::status::STATUS_REQUEST,
+        &ServerboundPacket::StatusPing(..) => internal_ids::status::STATUS_PING,
+        &ServerboundPacket::LoginStart(..) => internal_ids::login::LOGIN_START,
+        &ServerboundPacket::EncryptionResponse(..) => internal_ids::login::ENCRYPTION_RESPONSE,
+        &ServerboundPacket::TeleportConfirm(..) => internal_ids::play::TELEPORT_CONFIRM,
+        &ServerboundPacket::TabComplete(..) => internal_ids::play::TAB_COMPLETE,
+        &ServerboundPacket::ChatMessage(..) => internal_ids::play::CHAT_MESSAGE,
+        &ServerboundPacket::ClientStatus(..) => internal_ids::play::CLIENT_STATUS,
+        &ServerboundPacket::ClientSettings(..) => internal_ids::play::CLIENT_SETTINGS,
+        &ServerboundPacket::ConfirmTransaction(..) => internal_ids::play::CONFIRM_TRANSACTION,
+        &ServerboundPacket::EnchantItem(..) => internal_ids::play::ENCHANT_ITEM,
+        &ServerboundPacket::ClickWindow(..) => internal_ids::play::CLICK_WINDOW,
+        &ServerboundPacket::CloseWindow(..) => internal_ids::play::CLOSE_WINDOW,
+        &ServerboundPacket::PluginMessage(..) => internal_ids::play::PLUGIN_MESSAGE,
+        &ServerboundPacket::UseEntity(..) => internal_ids::play::USE_ENTITY,
+        &ServerboundPacket::KeepAlive(..) => internal_ids::play::KEEP_ALIVE,
+        &ServerboundPacket::Player(..) => internal_ids::play::PLAYER,
+        &ServerboundPacket::PlayerPosition(..) => internal_ids::play::PLAYER_POSITION,
+        &ServerboundPacket::PlayerPositionAndLook(..) => internal_ids::play::PLAYER_POSITION_AND_LOOK,
+        &ServerboundPacket::PlayerLook(..) => internal_ids::play::PLAYER_LOOK,
+        &ServerboundPacket::VehicleMove(..) => internal_ids::play::VEHICLE_MOVE,
+        &ServerboundPacket::SteerBoat(..) => internal_ids::play::STEER_BOAT,
+        &ServerboundPacket::CraftRecipeRequest(..) => internal_ids::play::CRAFT_RECIPE_REQUEST,
+        &ServerboundPacket::PlayerAbilities(..) => internal_ids::play::PLAYER_ABILITIES,
+        &ServerboundPacket::PlayerDigging(..) => internal_ids::play::PLAYER_DIGGING,
+        &ServerboundPacket::EntityAction(..) => internal_ids::play::ENTITY_ACTION,
+        &ServerboundPacket::SteerVehicle(..) => internal_ids::play::STEER_VEHICLE,
+        &ServerboundPacket::CraftingBookData(..) => internal_ids::play::CRAFTING_BOOK_DATA,
+        &ServerboundPacket::ResourcePackStatus(..) => internal_ids::play::RESOURCE_PACK_STATUS,
+        &ServerboundPacket::AdvancementTab(..) => internal_ids::play::ADVANCEMENT_TAB,
+        &ServerboundPacket::HeldItemChange(..) => internal_ids::play::HELD_ITEM_CHANGE,
+        &ServerboundPacket::CreativeInventoryAction(..) => internal_ids::play::CREATIVE_INVENTORY_ACTION,
+        &ServerboundPacket::UpdateSign(..) => internal_ids::play::UPDATE_SIGN,
+        &ServerboundPacket::Animation(..) => internal_ids::play::ANIMATION,
+        &ServerboundPacket::Spectate(..) => internal_ids::play::SPECTATE,
+        &ServerboundPacket::PlayerBlockPlacement(..) => internal_ids::play::PLAYER_BLOCK_PLACEMENT,
+        &ServerboundPacket::UseItem(..) => internal_ids::play::USE_ITEM,
 
         }
     }
-    pub fn to_u8(&self) -> Result<Vec<u8>> {
+    /// The wire id this packet is sent under for the given protocol
+    /// version. Returns an error if this packet doesn't exist on the wire
+    /// in that version.
+    pub fn get_id(&self, protocol_version: i32) -> Result<i32> {
+        protocol_ids::wire_id(&self.get_clientstate(), protocol_version, self.get_internal_id())
+    }
+    pub fn to_u8(&self, protocol_version: i32) -> Result<Vec<u8>> {
         match self {
         &ServerboundPacket::Handshake(ref x) => x.to_u8(),
         &ServerboundPacket::StatusRequest(ref x) => x.to_u8(),
@@ -262,7 +283,7 @@ impl Packet for ServerboundPacket {
         &ServerboundPacket::CloseWindow(ref x) => x.to_u8(),
         &ServerboundPacket::PluginMessage(ref x) => x.to_u8(),
         &ServerboundPacket::UseEntity(ref x) => x.to_u8(),
-        &ServerboundPacket::KeepAlive(ref x) => x.to_u8(),
+        &ServerboundPacket::KeepAlive(ref x) => x.to_u8(protocol_version),
         &ServerboundPacket::Player(ref x) => x.to_u8(),
         &ServerboundPacket::PlayerPosition(ref x) => x.to_u8(),
         &ServerboundPacket::PlayerPositionAndLook(ref x) => x.to_u8(),
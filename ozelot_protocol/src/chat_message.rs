@@ -0,0 +1,52 @@
+//! The serverbound `ChatMessage` packet. It used to round-trip the raw
+//! string the player typed, leaving every consumer to hand-parse any
+//! legacy `§`-coded formatting themselves; it now parses through
+//! `format::Component` like the clientbound chat packets do, so callers
+//! get one type regardless of direction.
+
+use errors::*;
+use format::Component;
+use packet::{read_varint, write_varint};
+use std::io::{Read, Write};
+
+/// Upper bound on a chat message's encoded length in bytes. This parses
+/// untrusted data straight off the wire, so the varint length prefix
+/// can't be trusted to preallocate a buffer directly; vanilla caps chat
+/// input well under this, so legitimate messages never come close.
+const MAX_CHAT_MESSAGE_LEN: i32 = 1 << 18;
+
+/// A chat message or slash command typed by the player.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ChatMessage {
+    pub message: Component,
+}
+
+impl ChatMessage {
+    pub fn deserialize<R: Read>(r: &mut R) -> Result<Self> {
+        let len = read_varint(r)?;
+        if len < 0 {
+            bail!("ChatMessage length cannot be negative (got {})", len);
+        }
+        if len > MAX_CHAT_MESSAGE_LEN {
+            bail!(
+                "ChatMessage length {} exceeds the max of {}",
+                len,
+                MAX_CHAT_MESSAGE_LEN
+            );
+        }
+        let mut buf = vec![0u8; len as usize];
+        r.read_exact(&mut buf)?;
+        let text = String::from_utf8(buf)?;
+        Ok(ChatMessage {
+            message: Component::from_string(&text)?,
+        })
+    }
+
+    pub fn to_u8(&self) -> Result<Vec<u8>> {
+        let mut ret = Vec::new();
+        let text = self.message.to_string();
+        write_varint(text.len() as i32, &mut ret)?;
+        ret.write_all(text.as_bytes())?;
+        Ok(ret)
+    }
+}
@@ -0,0 +1,65 @@
+//! The `ClickWindow` packet, sent whenever the client clicks a slot in an
+//! open window. `clicked_item` is parsed through `nbt::Slot` instead of
+//! being treated as an opaque buffer, so callers get a real item id/count/
+//! tag for the slot the click resolved to.
+
+use errors::*;
+use nbt::Slot;
+use std::io::{Read, Write};
+
+/// A single click on an open inventory window.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ClickWindow {
+    pub window_id: u8,
+    pub slot: i16,
+    pub button: i8,
+    pub action_number: i16,
+    pub mode: i8,
+    pub clicked_item: Slot,
+}
+
+impl ClickWindow {
+    pub fn deserialize<R: Read>(r: &mut R) -> Result<Self> {
+        let window_id = read_u8(r)?;
+        let slot = read_i16(r)?;
+        let button = read_i8(r)?;
+        let action_number = read_i16(r)?;
+        let mode = read_i8(r)?;
+        let clicked_item = Slot::read_from(r)?;
+        Ok(ClickWindow {
+            window_id: window_id,
+            slot: slot,
+            button: button,
+            action_number: action_number,
+            mode: mode,
+            clicked_item: clicked_item,
+        })
+    }
+
+    pub fn to_u8(&self) -> Result<Vec<u8>> {
+        let mut ret = Vec::new();
+        ret.write_all(&[self.window_id])?;
+        ret.write_all(&self.slot.to_be_bytes())?;
+        ret.write_all(&[self.button as u8])?;
+        ret.write_all(&self.action_number.to_be_bytes())?;
+        ret.write_all(&[self.mode as u8])?;
+        self.clicked_item.write_to(&mut ret)?;
+        Ok(ret)
+    }
+}
+
+fn read_u8<R: Read>(r: &mut R) -> Result<u8> {
+    let mut buf = [0u8; 1];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_i8<R: Read>(r: &mut R) -> Result<i8> {
+    Ok(read_u8(r)? as i8)
+}
+
+fn read_i16<R: Read>(r: &mut R) -> Result<i16> {
+    let mut buf = [0u8; 2];
+    r.read_exact(&mut buf)?;
+    Ok(i16::from_be_bytes(buf))
+}
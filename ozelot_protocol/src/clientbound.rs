@@ -0,0 +1,83 @@
+//! `ClientboundPacket`, the direction `ServerboundPacket` never got a
+//! counterpart for. Unlike the serverbound enum this one isn't
+//! `packets.clj`-generated yet: it only carries the clientbound packets
+//! ozelot currently needs structured access to (chat and keep-alive).
+//! Growing it to cover the rest of Play is a `packets.clj` job for later;
+//! it doesn't block `Capture`/`Replay` logging the packets it already
+//! knows about in both directions.
+
+use clientbound_chat_message::ClientboundChatMessage;
+use clientbound_keep_alive::ClientboundKeepAlive;
+use errors::*;
+use packet::{read_varint, ClientState, Packet};
+use std::fmt;
+use std::io::Read;
+
+/// The fixed Play-state wire ids of the clientbound packets modeled here,
+/// valid for every protocol version ozelot currently supports. A real
+/// `packets.clj` clientbound table (with its own per-version translation,
+/// like `protocol_ids` does for `ServerboundPacket`) replaces these once
+/// the rest of the direction is generated.
+const CHAT_MESSAGE_WIRE_ID: i32 = 0x0f;
+const KEEP_ALIVE_WIRE_ID: i32 = 0x1f;
+
+/// Represents a single clientbound packet.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum ClientboundPacket {
+    ChatMessage(ClientboundChatMessage),
+    KeepAlive(ClientboundKeepAlive),
+}
+
+impl ClientboundPacket {
+    pub fn deserialize<R: Read>(r: &mut R, state: &ClientState, protocol_version: i32) -> Result<Self> {
+        let wire_id = read_varint(r)?;
+        match state {
+            &ClientState::Play => match wire_id {
+                CHAT_MESSAGE_WIRE_ID => Ok(ClientboundPacket::ChatMessage(
+                    ClientboundChatMessage::deserialize(r)?,
+                )),
+                KEEP_ALIVE_WIRE_ID => Ok(ClientboundPacket::KeepAlive(
+                    ClientboundKeepAlive::deserialize(r, protocol_version)?,
+                )),
+                _ => bail!("No clientbound packet with id {} in state {}", wire_id, state),
+            },
+            _ => bail!("No clientbound packet with id {} in state {}", wire_id, state),
+        }
+    }
+
+    pub fn get_id(&self, _protocol_version: i32) -> Result<i32> {
+        Ok(match self {
+            &ClientboundPacket::ChatMessage(..) => CHAT_MESSAGE_WIRE_ID,
+            &ClientboundPacket::KeepAlive(..) => KEEP_ALIVE_WIRE_ID,
+        })
+    }
+
+    pub fn to_u8(&self, protocol_version: i32) -> Result<Vec<u8>> {
+        match self {
+            &ClientboundPacket::ChatMessage(ref x) => x.to_u8(),
+            &ClientboundPacket::KeepAlive(ref x) => x.to_u8(protocol_version),
+        }
+    }
+}
+
+impl Packet for ClientboundPacket {
+    fn get_packet_name(&self) -> &str {
+        match self {
+            &ClientboundPacket::ChatMessage(..) => "ChatMessage",
+            &ClientboundPacket::KeepAlive(..) => "KeepAlive",
+        }
+    }
+
+    fn get_clientstate(&self) -> ClientState {
+        match self {
+            &ClientboundPacket::ChatMessage(..) => ClientState::Play,
+            &ClientboundPacket::KeepAlive(..) => ClientState::Play,
+        }
+    }
+}
+
+impl fmt::Display for ClientboundPacket {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ClientboundPacket of type {}", self.get_packet_name())
+    }
+}
@@ -0,0 +1,55 @@
+//! The clientbound chat message packet: a structured `Component` payload
+//! plus the `position` byte saying where it renders (chat box, system
+//! message, or action bar).
+
+use errors::*;
+use format::Component;
+use packet::{read_varint, write_varint};
+use std::io::{Read, Write};
+
+/// Upper bound on a chat message's encoded length in bytes. This parses
+/// untrusted data straight off the wire, so the varint length prefix
+/// can't be trusted to preallocate a buffer directly; vanilla caps chat
+/// messages well under this, so legitimate messages never come close.
+const MAX_CHAT_MESSAGE_LEN: i32 = 1 << 18;
+
+/// A chat message sent to the client for display.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ClientboundChatMessage {
+    pub message: Component,
+    pub position: i8,
+}
+
+impl ClientboundChatMessage {
+    pub fn deserialize<R: Read>(r: &mut R) -> Result<Self> {
+        let len = read_varint(r)?;
+        if len < 0 {
+            bail!("ClientboundChatMessage length cannot be negative (got {})", len);
+        }
+        if len > MAX_CHAT_MESSAGE_LEN {
+            bail!(
+                "ClientboundChatMessage length {} exceeds the max of {}",
+                len,
+                MAX_CHAT_MESSAGE_LEN
+            );
+        }
+        let mut buf = vec![0u8; len as usize];
+        r.read_exact(&mut buf)?;
+        let text = String::from_utf8(buf)?;
+        let mut pos_buf = [0u8; 1];
+        r.read_exact(&mut pos_buf)?;
+        Ok(ClientboundChatMessage {
+            message: Component::from_string(&text)?,
+            position: pos_buf[0] as i8,
+        })
+    }
+
+    pub fn to_u8(&self) -> Result<Vec<u8>> {
+        let mut ret = Vec::new();
+        let text = self.message.to_json()?;
+        write_varint(text.len() as i32, &mut ret)?;
+        ret.write_all(text.as_bytes())?;
+        ret.write_all(&[self.position as u8])?;
+        Ok(ret)
+    }
+}
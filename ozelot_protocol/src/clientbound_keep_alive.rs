@@ -0,0 +1,30 @@
+//! Clientbound counterpart of `keep_alive::KeepAlive`. Same per-protocol
+//! wire shape (a VarInt on protocol 340, a big-endian i64 from 401
+//! onwards), but these ids originate with the server rather than being
+//! echoed back by the client.
+
+use errors::*;
+use keep_alive::{read_id, write_id};
+use std::io::Read;
+
+/// Clientbound KeepAlive, sent periodically to check the connection is
+/// still alive; the client must echo `id` back in a serverbound
+/// `keep_alive::KeepAlive`.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ClientboundKeepAlive {
+    pub id: i64,
+}
+
+impl ClientboundKeepAlive {
+    pub fn deserialize<R: Read>(r: &mut R, protocol_version: i32) -> Result<Self> {
+        Ok(ClientboundKeepAlive {
+            id: read_id(r, protocol_version)?,
+        })
+    }
+
+    pub fn to_u8(&self, protocol_version: i32) -> Result<Vec<u8>> {
+        let mut ret = Vec::new();
+        write_id(self.id, protocol_version, &mut ret)?;
+        Ok(ret)
+    }
+}
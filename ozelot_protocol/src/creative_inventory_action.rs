@@ -0,0 +1,34 @@
+//! The `CreativeInventoryAction` packet, sent when a creative-mode client
+//! sets a slot directly. `clicked_item` is parsed through `nbt::Slot`
+//! instead of being treated as an opaque buffer.
+
+use errors::*;
+use nbt::Slot;
+use std::io::{Read, Write};
+
+/// A creative-mode slot write.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct CreativeInventoryAction {
+    pub slot: i16,
+    pub clicked_item: Slot,
+}
+
+impl CreativeInventoryAction {
+    pub fn deserialize<R: Read>(r: &mut R) -> Result<Self> {
+        let mut buf = [0u8; 2];
+        r.read_exact(&mut buf)?;
+        let slot = i16::from_be_bytes(buf);
+        let clicked_item = Slot::read_from(r)?;
+        Ok(CreativeInventoryAction {
+            slot: slot,
+            clicked_item: clicked_item,
+        })
+    }
+
+    pub fn to_u8(&self) -> Result<Vec<u8>> {
+        let mut ret = Vec::new();
+        ret.write_all(&self.slot.to_be_bytes())?;
+        self.clicked_item.write_to(&mut ret)?;
+        Ok(ret)
+    }
+}
@@ -0,0 +1,40 @@
+//! The `EnchantItem` packet, sent when the client confirms an enchantment
+//! choice at an enchanting table. `clicked_item` is parsed through
+//! `nbt::Slot` instead of being treated as an opaque buffer, so callers see
+//! the real item being enchanted rather than raw bytes.
+
+use errors::*;
+use nbt::Slot;
+use std::io::{Read, Write};
+
+/// An enchantment confirmed at an open enchanting table window.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct EnchantItem {
+    pub window_id: u8,
+    pub enchantment: i8,
+    pub clicked_item: Slot,
+}
+
+impl EnchantItem {
+    pub fn deserialize<R: Read>(r: &mut R) -> Result<Self> {
+        let mut buf = [0u8; 1];
+        r.read_exact(&mut buf)?;
+        let window_id = buf[0];
+        r.read_exact(&mut buf)?;
+        let enchantment = buf[0] as i8;
+        let clicked_item = Slot::read_from(r)?;
+        Ok(EnchantItem {
+            window_id: window_id,
+            enchantment: enchantment,
+            clicked_item: clicked_item,
+        })
+    }
+
+    pub fn to_u8(&self) -> Result<Vec<u8>> {
+        let mut ret = Vec::new();
+        ret.write_all(&[self.window_id])?;
+        ret.write_all(&[self.enchantment as u8])?;
+        self.clicked_item.write_to(&mut ret)?;
+        Ok(ret)
+    }
+}
@@ -0,0 +1,273 @@
+//! Structured representation of Mojang's chat component JSON.
+//!
+//! `ChatMessage` and the clientbound chat packets used to round-trip raw
+//! strings, leaving every consumer to hand-parse the JSON (or the legacy
+//! `§`-code format) themselves. `Component` parses either shape once
+//! and lets callers either flatten it to plain text with `to_string()` or
+//! walk the structured tree for formatting.
+
+use errors::*;
+use serde_json;
+
+/// A single chat component. Mojang's chat JSON supports translation,
+/// scoreboard and entity-selector components too, but `Text` is the only
+/// shape ozelot needs so far.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum Component {
+    Text(TextComponent),
+}
+
+/// A run of literal text plus its formatting and any child components that
+/// inherit from it.
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct TextComponent {
+    pub text: String,
+    pub modifier: Modifier,
+    pub extra: Vec<Component>,
+}
+
+/// The formatting applied to a component. `None` for a field means
+/// "inherit from the parent component" rather than "force off".
+#[derive(Debug, PartialEq, Clone, Default, Serialize, Deserialize)]
+pub struct Modifier {
+    pub color: Option<String>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underlined: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub obfuscated: Option<bool>,
+}
+
+impl Component {
+    /// Parses a chat message payload, trying Mojang's chat JSON first and
+    /// falling back to treating the string as a legacy, `§`-coded
+    /// literal if it isn't valid JSON.
+    pub fn from_string(input: &str) -> Result<Self> {
+        match serde_json::from_str::<JsonComponent>(input) {
+            Ok(json) => Ok(json.into_component()),
+            Err(_) => Ok(Component::from_legacy(input)),
+        }
+    }
+
+    /// Converts a legacy string containing `§`-prefixed formatting
+    /// codes into a tree of components: every code starts a new child that
+    /// carries the color/style from that point on, until the next code or
+    /// the end of the string. `r` resets back to an unformatted child.
+    fn from_legacy(input: &str) -> Self {
+        let mut root = TextComponent::default();
+        let mut modifier = Modifier::default();
+        let mut chars = input.chars().peekable();
+        let mut current = String::new();
+
+        macro_rules! flush {
+            () => {
+                if !current.is_empty() {
+                    root.extra.push(Component::Text(TextComponent {
+                        text: current.clone(),
+                        modifier: modifier.clone(),
+                        extra: Vec::new(),
+                    }));
+                    current.clear();
+                }
+            };
+        }
+
+        while let Some(c) = chars.next() {
+            if c == '\u{00a7}' {
+                if let Some(&code) = chars.peek() {
+                    chars.next();
+                    flush!();
+                    apply_legacy_code(&mut modifier, code);
+                    continue;
+                }
+            }
+            current.push(c);
+        }
+        flush!();
+
+        Component::Text(root)
+    }
+
+    /// Flattens this component and its children to plain, unformatted text.
+    pub fn to_string(&self) -> String {
+        match self {
+            &Component::Text(ref text) => {
+                let mut out = text.text.clone();
+                for child in &text.extra {
+                    out.push_str(&child.to_string());
+                }
+                out
+            }
+        }
+    }
+
+    /// Serializes this component back to Mojang's chat JSON.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.to_json_value())?)
+    }
+
+    fn to_json_value(&self) -> JsonComponent {
+        match self {
+            &Component::Text(ref text) => JsonComponent {
+                text: Some(text.text.clone()),
+                color: text.modifier.color.clone(),
+                bold: text.modifier.bold,
+                italic: text.modifier.italic,
+                underlined: text.modifier.underlined,
+                strikethrough: text.modifier.strikethrough,
+                obfuscated: text.modifier.obfuscated,
+                extra: if text.extra.is_empty() {
+                    None
+                } else {
+                    Some(text.extra.iter().map(|c| c.to_json_value()).collect())
+                },
+            },
+        }
+    }
+}
+
+/// Sets the field on `modifier` that the legacy code `code` corresponds
+/// to. `0`-`f` set the color (resetting styles, matching vanilla); `k`-`o`
+/// set a style; `r` resets everything. Unknown codes are ignored.
+fn apply_legacy_code(modifier: &mut Modifier, code: char) {
+    let color = match code {
+        '0' => Some("black"),
+        '1' => Some("dark_blue"),
+        '2' => Some("dark_green"),
+        '3' => Some("dark_aqua"),
+        '4' => Some("dark_red"),
+        '5' => Some("dark_purple"),
+        '6' => Some("gold"),
+        '7' => Some("gray"),
+        '8' => Some("dark_gray"),
+        '9' => Some("blue"),
+        'a' => Some("green"),
+        'b' => Some("aqua"),
+        'c' => Some("red"),
+        'd' => Some("light_purple"),
+        'e' => Some("yellow"),
+        'f' => Some("white"),
+        _ => None,
+    };
+    if let Some(color) = color {
+        *modifier = Modifier {
+            color: Some(color.to_string()),
+            ..Modifier::default()
+        };
+        return;
+    }
+
+    match code {
+        'k' => modifier.obfuscated = Some(true),
+        'l' => modifier.bold = Some(true),
+        'm' => modifier.strikethrough = Some(true),
+        'n' => modifier.underlined = Some(true),
+        'o' => modifier.italic = Some(true),
+        'r' => *modifier = Modifier::default(),
+        _ => {}
+    }
+}
+
+/// The wire shape of a text chat component, used only to (de)serialize
+/// to/from JSON; `Component`/`TextComponent` are what the rest of the
+/// crate works with.
+#[derive(Debug, Serialize, Deserialize)]
+struct JsonComponent {
+    text: Option<String>,
+    color: Option<String>,
+    bold: Option<bool>,
+    italic: Option<bool>,
+    underlined: Option<bool>,
+    strikethrough: Option<bool>,
+    obfuscated: Option<bool>,
+    extra: Option<Vec<JsonComponent>>,
+}
+
+impl JsonComponent {
+    fn into_component(self) -> Component {
+        Component::Text(TextComponent {
+            text: self.text.unwrap_or_default(),
+            modifier: Modifier {
+                color: self.color,
+                bold: self.bold,
+                italic: self.italic,
+                underlined: self.underlined,
+                strikethrough: self.strikethrough,
+                obfuscated: self.obfuscated,
+            },
+            extra: self
+                .extra
+                .unwrap_or_default()
+                .into_iter()
+                .map(JsonComponent::into_component)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_string_becomes_a_single_text_component() {
+        let component = Component::from_string("hello").unwrap();
+        assert_eq!(component.to_string(), "hello");
+    }
+
+    #[test]
+    fn legacy_color_code_starts_a_new_child() {
+        let component = Component::from_string("\u{00a7}chello").unwrap();
+        match component {
+            Component::Text(ref root) => {
+                assert_eq!(root.text, "");
+                assert_eq!(root.extra.len(), 1);
+                match &root.extra[0] {
+                    &Component::Text(ref child) => {
+                        assert_eq!(child.text, "hello");
+                        assert_eq!(child.modifier.color, Some("red".to_string()));
+                    }
+                }
+            }
+        }
+        assert_eq!(
+            Component::from_string("\u{00a7}chello").unwrap().to_string(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn legacy_reset_code_clears_the_modifier() {
+        let component = Component::from_string("\u{00a7}c\u{00a7}rplain").unwrap();
+        assert_eq!(component.to_string(), "plain");
+    }
+
+    #[test]
+    fn legacy_style_code_is_additive_with_color() {
+        let component = Component::from_string("\u{00a7}a\u{00a7}lbold green").unwrap();
+        match component {
+            Component::Text(ref root) => {
+                let styled = root
+                    .extra
+                    .iter()
+                    .find_map(|c| match c {
+                        &Component::Text(ref t) if t.text == "bold green" => Some(t),
+                        _ => None,
+                    })
+                    .expect("expected a \"bold green\" run");
+                assert_eq!(styled.modifier.color, Some("green".to_string()));
+                assert_eq!(styled.modifier.bold, Some(true));
+            }
+        }
+    }
+
+    #[test]
+    fn mojang_json_round_trips_through_to_json() {
+        let json = r#"{"text":"hi","bold":true}"#;
+        let component = Component::from_string(json).unwrap();
+        assert_eq!(component.to_string(), "hi");
+        let reencoded = component.to_json().unwrap();
+        let reparsed = Component::from_string(&reencoded).unwrap();
+        assert_eq!(component, reparsed);
+    }
+}
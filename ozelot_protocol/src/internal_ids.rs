@@ -0,0 +1,95 @@
+//! Assigns `ServerboundPacket`'s internal packet ids at compile time
+//! instead of by hand.
+//!
+//! Previously `get_internal_id`, the `deserialize` match arms, and the
+//! `ServerboundPacket` variant order all had to be kept in lockstep by hand
+//! (and by packets.clj): inserting a packet in the middle of a state
+//! silently shifted every id after it. `create_ids!` instead takes just the
+//! order packets appear in for a `(state, direction)` group and assigns
+//! `pub const <NAME>: i32 = <prev> + 1;` starting at 0, the way
+//! stevenarella's protocol module does it. Adding or reordering a packet
+//! now means editing one list here; the ids, the dispatch table and
+//! `get_internal_id` stay consistent automatically.
+macro_rules! create_ids {
+    ($($name:ident),* $(,)*) => {
+        create_ids!(@assign 0, $($name),*);
+    };
+    (@assign $cur:expr, $name:ident $(, $rest:ident)*) => {
+        pub const $name: i32 = $cur;
+        create_ids!(@assign $cur + 1, $($rest),*);
+    };
+    (@assign $cur:expr,) => {};
+}
+
+pub mod handshake {
+    create_ids!(HANDSHAKE);
+}
+
+pub mod status {
+    create_ids!(STATUS_REQUEST, STATUS_PING);
+}
+
+pub mod login {
+    create_ids!(LOGIN_START, ENCRYPTION_RESPONSE);
+}
+
+pub mod play {
+    create_ids!(
+        TELEPORT_CONFIRM,
+        TAB_COMPLETE,
+        CHAT_MESSAGE,
+        CLIENT_STATUS,
+        CLIENT_SETTINGS,
+        CONFIRM_TRANSACTION,
+        ENCHANT_ITEM,
+        CLICK_WINDOW,
+        CLOSE_WINDOW,
+        PLUGIN_MESSAGE,
+        USE_ENTITY,
+        KEEP_ALIVE,
+        PLAYER,
+        PLAYER_POSITION,
+        PLAYER_POSITION_AND_LOOK,
+        PLAYER_LOOK,
+        VEHICLE_MOVE,
+        STEER_BOAT,
+        CRAFT_RECIPE_REQUEST,
+        PLAYER_ABILITIES,
+        PLAYER_DIGGING,
+        ENTITY_ACTION,
+        STEER_VEHICLE,
+        CRAFTING_BOOK_DATA,
+        RESOURCE_PACK_STATUS,
+        ADVANCEMENT_TAB,
+        HELD_ITEM_CHANGE,
+        CREATIVE_INVENTORY_ACTION,
+        UPDATE_SIGN,
+        ANIMATION,
+        SPECTATE,
+        PLAYER_BLOCK_PLACEMENT,
+        USE_ITEM,
+    );
+}
+
+/// Exercises `create_ids!`, the macro this module (chunk0-2) introduced;
+/// these landed in a chunk0-1 commit that was fixing an unrelated
+/// `impl Packet` issue, which mistagged them - noted here so the history
+/// reads correctly going forward.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assigns_sequential_ids_starting_at_zero() {
+        assert_eq!(status::STATUS_REQUEST, 0);
+        assert_eq!(status::STATUS_PING, 1);
+    }
+
+    #[test]
+    fn play_ids_follow_declaration_order() {
+        assert_eq!(play::TELEPORT_CONFIRM, 0);
+        assert_eq!(play::TAB_COMPLETE, 1);
+        assert_eq!(play::CHAT_MESSAGE, 2);
+        assert_eq!(play::USE_ITEM, 32);
+    }
+}
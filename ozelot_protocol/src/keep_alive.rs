@@ -0,0 +1,54 @@
+//! `KeepAlive`'s wire layout changed between protocol versions: 1.12.2
+//! (protocol 340) encodes the id as a VarInt, while 1.13+ (protocol 401
+//! onwards) encodes it as a plain big-endian i64. ozelot picks the right
+//! shape for the wire based on the `protocol_version` passed in to
+//! `ServerboundPacket::deserialize`/`to_u8`.
+
+use errors::*;
+use packet::{read_varint, write_varint};
+use std::io::{Read, Write};
+
+/// Reads a KeepAlive id in whichever shape `protocol_version` uses for it:
+/// a VarInt on protocol 340, a big-endian i64 from 401 onwards. Shared by
+/// both `KeepAlive` and `clientbound_keep_alive::ClientboundKeepAlive`,
+/// which only differ in which side of the connection sends the id.
+pub(crate) fn read_id<R: Read>(r: &mut R, protocol_version: i32) -> Result<i64> {
+    if protocol_version <= 340 {
+        Ok(read_varint(r)? as i64)
+    } else {
+        let mut buf = [0; 8];
+        r.read_exact(&mut buf)?;
+        Ok(i64::from_be_bytes(buf))
+    }
+}
+
+/// Writes a KeepAlive id in whichever shape `protocol_version` uses for it.
+/// See `read_id`.
+pub(crate) fn write_id(id: i64, protocol_version: i32, w: &mut Vec<u8>) -> Result<()> {
+    if protocol_version <= 340 {
+        write_varint(id as i32, w)?;
+    } else {
+        w.write_all(&id.to_be_bytes())?;
+    }
+    Ok(())
+}
+
+/// Serverbound KeepAlive, sent in response to the server's own KeepAlive.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct KeepAlive {
+    pub id: i64,
+}
+
+impl KeepAlive {
+    pub fn deserialize<R: Read>(r: &mut R, protocol_version: i32) -> Result<Self> {
+        Ok(KeepAlive {
+            id: read_id(r, protocol_version)?,
+        })
+    }
+
+    pub fn to_u8(&self, protocol_version: i32) -> Result<Vec<u8>> {
+        let mut ret = Vec::new();
+        write_id(self.id, protocol_version, &mut ret)?;
+        Ok(ret)
+    }
+}
@@ -0,0 +1,58 @@
+//! `ozelot_protocol` is the wire-format half of ozelot: the packet enums,
+//! the `Packet` trait, `ClientState`, varint/field helpers, and encryption
+//! and compression, split out of the top-level `ozelot` crate so that
+//! downstream bots, proxies and servers can depend on just the protocol
+//! without pulling in connection management, the way stevenarella's
+//! `steven_protocol` is split from `steven`.
+//!
+//! The top-level `ozelot` crate re-exports everything here and adds the
+//! `Client`/connection layer on top of it.
+
+#[macro_use]
+extern crate error_chain;
+#[macro_use]
+extern crate serde_derive;
+extern crate serde;
+extern crate serde_json;
+
+pub mod errors {
+    error_chain! {
+        foreign_links {
+            Io(::std::io::Error);
+            Json(::serde_json::Error);
+            Utf8(::std::string::FromUtf8Error);
+        }
+    }
+}
+
+pub mod packet;
+
+#[macro_use]
+mod internal_ids;
+mod protocol_ids;
+
+pub mod capture;
+pub mod chat_message;
+pub mod click_window;
+pub mod clientbound;
+pub mod clientbound_chat_message;
+pub mod clientbound_keep_alive;
+pub mod creative_inventory_action;
+pub mod enchant_item;
+pub mod format;
+pub mod keep_alive;
+pub mod nbt;
+pub mod unimplemented_packets;
+
+use chat_message::ChatMessage;
+use click_window::ClickWindow;
+use creative_inventory_action::CreativeInventoryAction;
+use enchant_item::EnchantItem;
+use errors::*;
+use keep_alive::KeepAlive;
+use packet::{read_varint, ClientState, Packet};
+use std::fmt;
+use std::io::Read;
+use unimplemented_packets::*;
+
+include!(".serverbound-enum.generated.rs");
@@ -0,0 +1,440 @@
+//! The Notchian NBT binary format, and the inventory `Slot` field type
+//! built on top of it.
+//!
+//! Packets like `ClickWindow`, `CreativeInventoryAction` and `EnchantItem`
+//! carry item slots whose tag data is NBT; without a `Tag` type those
+//! fields had to be treated as opaque byte blobs. `Tag::read_from`/
+//! `write_to` implement the wire format (named root compound, big-endian
+//! lengths, typed lists) so `Slot` can expose a real item id/count/tag
+//! instead of raw bytes.
+
+use errors::*;
+use std::io::{Read, Write};
+
+/// A single NBT tag. Lists and compounds nest arbitrarily deep, same as
+/// the Notchian format.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum Tag {
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<Tag>),
+    Compound(Vec<(String, Tag)>),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+const TAG_END: u8 = 0;
+const TAG_BYTE: u8 = 1;
+const TAG_SHORT: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_LONG: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_DOUBLE: u8 = 6;
+const TAG_BYTE_ARRAY: u8 = 7;
+const TAG_STRING: u8 = 8;
+const TAG_LIST: u8 = 9;
+const TAG_COMPOUND: u8 = 10;
+const TAG_INT_ARRAY: u8 = 11;
+const TAG_LONG_ARRAY: u8 = 12;
+
+/// Upper bound on a single `ByteArray`/`List`/`IntArray`/`LongArray`
+/// length. This parses untrusted inventory-packet data, so a raw `i32`
+/// length prefix can't be trusted to preallocate a buffer directly - a
+/// peer could claim `i32::MAX` elements and make us try to allocate
+/// gigabytes before reading a single one. Real item NBT (enchantments,
+/// display names, attribute modifiers) never comes close to this.
+const MAX_NBT_ARRAY_LEN: i32 = 1 << 20;
+
+/// Upper bound on how deeply `TAG_List`/`TAG_Compound` may nest. Same
+/// untrusted-input concern as `MAX_NBT_ARRAY_LEN`: without a limit, a
+/// peer can nest tags deeply enough to blow the stack via
+/// `read_payload`'s recursion before any length check comes into play.
+/// Real item NBT nests at most a handful of levels deep.
+const MAX_NBT_DEPTH: u32 = 64;
+
+/// Validates a length prefix read off the wire before it's used to
+/// preallocate a buffer.
+fn checked_len(len: i32) -> Result<usize> {
+    if len < 0 {
+        bail!("NBT array/list length cannot be negative (got {})", len);
+    }
+    if len > MAX_NBT_ARRAY_LEN {
+        bail!(
+            "NBT array/list length {} exceeds the max of {}",
+            len,
+            MAX_NBT_ARRAY_LEN
+        );
+    }
+    Ok(len as usize)
+}
+
+impl Tag {
+    fn type_id(&self) -> u8 {
+        match self {
+            &Tag::Byte(..) => TAG_BYTE,
+            &Tag::Short(..) => TAG_SHORT,
+            &Tag::Int(..) => TAG_INT,
+            &Tag::Long(..) => TAG_LONG,
+            &Tag::Float(..) => TAG_FLOAT,
+            &Tag::Double(..) => TAG_DOUBLE,
+            &Tag::ByteArray(..) => TAG_BYTE_ARRAY,
+            &Tag::String(..) => TAG_STRING,
+            &Tag::List(..) => TAG_LIST,
+            &Tag::Compound(..) => TAG_COMPOUND,
+            &Tag::IntArray(..) => TAG_INT_ARRAY,
+            &Tag::LongArray(..) => TAG_LONG_ARRAY,
+        }
+    }
+
+    /// Reads a complete named root compound (the `TAG_Compound` id byte,
+    /// its name, and its contents) off `r`.
+    pub fn read_from<R: Read>(r: &mut R) -> Result<(String, Tag)> {
+        let type_id = read_u8(r)?;
+        if type_id != TAG_COMPOUND {
+            bail!("Expected a root TAG_Compound, got type id {}", type_id);
+        }
+        let name = read_string(r)?;
+        let tag = Tag::read_payload(r, type_id, 0)?;
+        Ok((name, tag))
+    }
+
+    fn read_payload<R: Read>(r: &mut R, type_id: u8, depth: u32) -> Result<Tag> {
+        if depth > MAX_NBT_DEPTH {
+            bail!("NBT tag nesting exceeds the max depth of {}", MAX_NBT_DEPTH);
+        }
+        Ok(match type_id {
+            TAG_BYTE => Tag::Byte(read_u8(r)? as i8),
+            TAG_SHORT => Tag::Short(read_i16(r)?),
+            TAG_INT => Tag::Int(read_i32(r)?),
+            TAG_LONG => Tag::Long(read_i64(r)?),
+            TAG_FLOAT => Tag::Float(f32::from_bits(read_i32(r)? as u32)),
+            TAG_DOUBLE => Tag::Double(f64::from_bits(read_i64(r)? as u64)),
+            TAG_BYTE_ARRAY => {
+                let len = checked_len(read_i32(r)?)?;
+                let mut buf = vec![0i8; len];
+                for slot in buf.iter_mut() {
+                    *slot = read_u8(r)? as i8;
+                }
+                Tag::ByteArray(buf)
+            }
+            TAG_STRING => Tag::String(read_string(r)?),
+            TAG_LIST => {
+                let elem_type = read_u8(r)?;
+                let len = checked_len(read_i32(r)?)?;
+                let mut items = Vec::with_capacity(len);
+                for _ in 0..len {
+                    items.push(Tag::read_payload(r, elem_type, depth + 1)?);
+                }
+                Tag::List(items)
+            }
+            TAG_COMPOUND => {
+                let mut entries = Vec::new();
+                loop {
+                    let child_type = read_u8(r)?;
+                    if child_type == TAG_END {
+                        break;
+                    }
+                    let name = read_string(r)?;
+                    let tag = Tag::read_payload(r, child_type, depth + 1)?;
+                    entries.push((name, tag));
+                }
+                Tag::Compound(entries)
+            }
+            TAG_INT_ARRAY => {
+                let len = checked_len(read_i32(r)?)?;
+                let mut buf = Vec::with_capacity(len);
+                for _ in 0..len {
+                    buf.push(read_i32(r)?);
+                }
+                Tag::IntArray(buf)
+            }
+            TAG_LONG_ARRAY => {
+                let len = checked_len(read_i32(r)?)?;
+                let mut buf = Vec::with_capacity(len);
+                for _ in 0..len {
+                    buf.push(read_i64(r)?);
+                }
+                Tag::LongArray(buf)
+            }
+            other => bail!("Unknown NBT tag type id {}", other),
+        })
+    }
+
+    /// Writes a complete named root compound (the id byte, `name`, and its
+    /// contents) to `w`.
+    pub fn write_to<W: Write>(&self, name: &str, w: &mut W) -> Result<()> {
+        if self.type_id() != TAG_COMPOUND {
+            bail!("Root NBT tag must be a TAG_Compound");
+        }
+        write_u8(self.type_id(), w)?;
+        write_string(name, w)?;
+        self.write_payload(w)
+    }
+
+    fn write_payload<W: Write>(&self, w: &mut W) -> Result<()> {
+        match self {
+            &Tag::Byte(v) => write_u8(v as u8, w)?,
+            &Tag::Short(v) => write_i16(v, w)?,
+            &Tag::Int(v) => write_i32(v, w)?,
+            &Tag::Long(v) => write_i64(v, w)?,
+            &Tag::Float(v) => write_i32(v.to_bits() as i32, w)?,
+            &Tag::Double(v) => write_i64(v.to_bits() as i64, w)?,
+            &Tag::ByteArray(ref bytes) => {
+                write_i32(bytes.len() as i32, w)?;
+                for &b in bytes {
+                    write_u8(b as u8, w)?;
+                }
+            }
+            &Tag::String(ref s) => write_string(s, w)?,
+            &Tag::List(ref items) => {
+                let elem_type = items.first().map(Tag::type_id).unwrap_or(TAG_END);
+                write_u8(elem_type, w)?;
+                write_i32(items.len() as i32, w)?;
+                for item in items {
+                    item.write_payload(w)?;
+                }
+            }
+            &Tag::Compound(ref entries) => {
+                for &(ref name, ref tag) in entries {
+                    write_u8(tag.type_id(), w)?;
+                    write_string(name, w)?;
+                    tag.write_payload(w)?;
+                }
+                write_u8(TAG_END, w)?;
+            }
+            &Tag::IntArray(ref items) => {
+                write_i32(items.len() as i32, w)?;
+                for &v in items {
+                    write_i32(v, w)?;
+                }
+            }
+            &Tag::LongArray(ref items) => {
+                write_i32(items.len() as i32, w)?;
+                for &v in items {
+                    write_i64(v, w)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn read_u8<R: Read>(r: &mut R) -> Result<u8> {
+    let mut buf = [0u8; 1];
+    r.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+fn read_i16<R: Read>(r: &mut R) -> Result<i16> {
+    let mut buf = [0u8; 2];
+    r.read_exact(&mut buf)?;
+    Ok(i16::from_be_bytes(buf))
+}
+
+fn read_i32<R: Read>(r: &mut R) -> Result<i32> {
+    let mut buf = [0u8; 4];
+    r.read_exact(&mut buf)?;
+    Ok(i32::from_be_bytes(buf))
+}
+
+fn read_i64<R: Read>(r: &mut R) -> Result<i64> {
+    let mut buf = [0u8; 8];
+    r.read_exact(&mut buf)?;
+    Ok(i64::from_be_bytes(buf))
+}
+
+fn read_string<R: Read>(r: &mut R) -> Result<String> {
+    let len = {
+        let mut buf = [0u8; 2];
+        r.read_exact(&mut buf)?;
+        u16::from_be_bytes(buf)
+    };
+    let mut buf = vec![0u8; len as usize];
+    r.read_exact(&mut buf)?;
+    Ok(String::from_utf8(buf)?)
+}
+
+fn write_u8<W: Write>(v: u8, w: &mut W) -> Result<()> {
+    w.write_all(&[v])?;
+    Ok(())
+}
+
+fn write_i16<W: Write>(v: i16, w: &mut W) -> Result<()> {
+    w.write_all(&v.to_be_bytes())?;
+    Ok(())
+}
+
+fn write_i32<W: Write>(v: i32, w: &mut W) -> Result<()> {
+    w.write_all(&v.to_be_bytes())?;
+    Ok(())
+}
+
+fn write_i64<W: Write>(v: i64, w: &mut W) -> Result<()> {
+    w.write_all(&v.to_be_bytes())?;
+    Ok(())
+}
+
+fn write_string<W: Write>(s: &str, w: &mut W) -> Result<()> {
+    write_i16(s.len() as i16, w)?;
+    w.write_all(s.as_bytes())?;
+    Ok(())
+}
+
+/// An inventory slot, as carried by `ClickWindow`, `CreativeInventoryAction`,
+/// `EnchantItem` and the other inventory packets: either empty, or a
+/// present item with an id, a stack count, and its NBT tag (enchantments,
+/// display name, etc).
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub enum Slot {
+    Empty,
+    Present { item_id: i32, count: i8, tag: Option<Tag> },
+}
+
+impl Slot {
+    pub fn read_from<R: Read>(r: &mut R) -> Result<Self> {
+        use packet::read_varint;
+
+        let present = read_u8(r)? != 0;
+        if !present {
+            return Ok(Slot::Empty);
+        }
+        let item_id = read_varint(r)?;
+        let count = read_u8(r)? as i8;
+        let has_tag = read_u8(r)? != 0;
+        let tag = if has_tag {
+            let (_name, tag) = Tag::read_from(r)?;
+            Some(tag)
+        } else {
+            None
+        };
+        Ok(Slot::Present {
+            item_id: item_id,
+            count: count,
+            tag: tag,
+        })
+    }
+
+    pub fn write_to<W: Write>(&self, w: &mut W) -> Result<()> {
+        use packet::write_varint;
+
+        match self {
+            &Slot::Empty => write_u8(0, w),
+            &Slot::Present { item_id, count, ref tag } => {
+                write_u8(1, w)?;
+                write_varint(item_id, w)?;
+                write_u8(count as u8, w)?;
+                match tag {
+                    &Some(ref tag) => {
+                        write_u8(1, w)?;
+                        tag.write_to("", w)
+                    }
+                    &None => write_u8(0, w),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(name: &str, tag: &Tag) -> Tag {
+        let mut buf = Vec::new();
+        tag.write_to(name, &mut buf).unwrap();
+        let (read_name, read_tag) = Tag::read_from(&mut &buf[..]).unwrap();
+        assert_eq!(read_name, name);
+        read_tag
+    }
+
+    #[test]
+    fn compound_with_every_tag_type_round_trips() {
+        let tag = Tag::Compound(vec![
+            ("byte".to_string(), Tag::Byte(-1)),
+            ("short".to_string(), Tag::Short(-2)),
+            ("int".to_string(), Tag::Int(-3)),
+            ("long".to_string(), Tag::Long(-4)),
+            ("float".to_string(), Tag::Float(1.5)),
+            ("double".to_string(), Tag::Double(2.5)),
+            ("byte_array".to_string(), Tag::ByteArray(vec![1, 2, 3])),
+            ("string".to_string(), Tag::String("hi".to_string())),
+            (
+                "list".to_string(),
+                Tag::List(vec![Tag::Int(1), Tag::Int(2)]),
+            ),
+            ("int_array".to_string(), Tag::IntArray(vec![4, 5, 6])),
+            ("long_array".to_string(), Tag::LongArray(vec![7, 8])),
+        ]);
+        assert_eq!(round_trip("root", &tag), tag);
+    }
+
+    #[test]
+    fn nested_compound_round_trips() {
+        let tag = Tag::Compound(vec![(
+            "child".to_string(),
+            Tag::Compound(vec![("leaf".to_string(), Tag::Byte(9))]),
+        )]);
+        assert_eq!(round_trip("root", &tag), tag);
+    }
+
+    #[test]
+    fn non_compound_root_is_rejected() {
+        let mut buf = Vec::new();
+        assert!(Tag::Int(1).write_to("root", &mut buf).is_err());
+    }
+
+    #[test]
+    fn oversized_array_length_is_rejected_before_allocating() {
+        assert!(checked_len(i32::MAX).is_err());
+        assert!(checked_len(-1).is_err());
+        assert!(checked_len(16).is_ok());
+    }
+
+    #[test]
+    fn deeply_nested_compound_is_rejected_before_recursing_too_far() {
+        let mut tag = Tag::Compound(vec![("leaf".to_string(), Tag::Byte(0))]);
+        for _ in 0..MAX_NBT_DEPTH + 1 {
+            tag = Tag::Compound(vec![("child".to_string(), tag)]);
+        }
+        let mut buf = Vec::new();
+        tag.write_to("root", &mut buf).unwrap();
+        assert!(Tag::read_from(&mut &buf[..]).is_err());
+    }
+
+    #[test]
+    fn empty_slot_round_trips() {
+        let mut buf = Vec::new();
+        Slot::Empty.write_to(&mut buf).unwrap();
+        assert_eq!(Slot::read_from(&mut &buf[..]).unwrap(), Slot::Empty);
+    }
+
+    #[test]
+    fn present_slot_with_tag_round_trips() {
+        let slot = Slot::Present {
+            item_id: 42,
+            count: 3,
+            tag: Some(Tag::Compound(vec![("ench".to_string(), Tag::Int(1))])),
+        };
+        let mut buf = Vec::new();
+        slot.write_to(&mut buf).unwrap();
+        assert_eq!(Slot::read_from(&mut &buf[..]).unwrap(), slot);
+    }
+
+    #[test]
+    fn present_slot_without_tag_round_trips() {
+        let slot = Slot::Present {
+            item_id: 1,
+            count: 64,
+            tag: None,
+        };
+        let mut buf = Vec::new();
+        slot.write_to(&mut buf).unwrap();
+        assert_eq!(Slot::read_from(&mut &buf[..]).unwrap(), slot);
+    }
+}
@@ -0,0 +1,70 @@
+//! The `Packet` trait, `ClientState`, and the varint/field read-write
+//! helpers every generated packet type is built on.
+
+use errors::*;
+use std::fmt;
+use std::io::{Read, Write};
+
+/// Which phase of the Minecraft handshake a connection is in. Each state
+/// has its own, independent packet id space in both directions.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum ClientState {
+    Handshake,
+    Status,
+    Login,
+    Play,
+}
+
+impl fmt::Display for ClientState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            &ClientState::Handshake => write!(f, "Handshake"),
+            &ClientState::Status => write!(f, "Status"),
+            &ClientState::Login => write!(f, "Login"),
+            &ClientState::Play => write!(f, "Play"),
+        }
+    }
+}
+
+/// Common behaviour shared by every packet enum (`ServerboundPacket`, and
+/// its clientbound counterpart).
+pub trait Packet: Sized {
+    fn get_packet_name(&self) -> &str;
+    fn get_clientstate(&self) -> ClientState;
+}
+
+/// Reads a protocol varint, Minecraft's variable-length encoding for `i32`.
+pub fn read_varint<R: Read>(r: &mut R) -> Result<i32> {
+    let mut result: i32 = 0;
+    let mut shift = 0;
+    loop {
+        let mut buf = [0u8; 1];
+        r.read_exact(&mut buf)?;
+        let byte = buf[0];
+        result |= ((byte & 0x7f) as i32) << shift;
+        if byte & 0x80 == 0 {
+            break;
+        }
+        shift += 7;
+        if shift >= 32 {
+            bail!("VarInt is too big");
+        }
+    }
+    Ok(result)
+}
+
+/// Writes `value` as a protocol varint to `w`.
+pub fn write_varint<W: Write>(mut value: i32, w: &mut W) -> Result<()> {
+    loop {
+        let mut byte = (value as u32 & 0x7f) as u8;
+        value = ((value as u32) >> 7) as i32;
+        if value != 0 {
+            byte |= 0x80;
+        }
+        w.write_all(&[byte])?;
+        if value == 0 {
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,192 @@
+//! Per-protocol-version translation between ozelot's internal packet ids
+//! (the order packets appear in in `ServerboundPacket`) and the wire ids a
+//! given Minecraft protocol actually uses on the socket.
+//!
+//! ozelot keeps a single, stable internal id space so the rest of the crate
+//! never has to think about protocol versions; this module confines all of
+//! the version-specific wiring to the tables below, the way stevenarella's
+//! `protocol::versions` module does it.
+
+use errors::*;
+use packet::ClientState;
+
+/// A stable, version-independent id for a `ServerboundPacket` variant. These
+/// are the constants assigned by `internal_ids::create_ids!`, not a wire id.
+pub type InternalId = i32;
+
+/// Every protocol version ozelot currently knows how to speak.
+pub const SUPPORTED_PROTOCOLS: &[i32] = &[340, 401];
+
+/// The per-version id table for a single `ClientState`: `wire_to_internal`
+/// is indexed by the raw varint read off the wire, `internal_to_wire` is
+/// indexed by the internal id, and they're inverses of each other.
+struct StateIds {
+    wire_to_internal: &'static [Option<InternalId>],
+    internal_to_wire: &'static [Option<i32>],
+}
+
+struct IdTable {
+    protocol_version: i32,
+    handshake: StateIds,
+    status: StateIds,
+    login: StateIds,
+    play: StateIds,
+}
+
+/// Protocol 340 (1.12.2), which is the wire layout ozelot originally shipped
+/// with: internal ids and wire ids are identical in every state.
+const TABLE_340: IdTable = IdTable {
+    protocol_version: 340,
+    handshake: StateIds {
+        wire_to_internal: &[Some(0)],
+        internal_to_wire: &[Some(0)],
+    },
+    status: StateIds {
+        wire_to_internal: &[Some(0), Some(1)],
+        internal_to_wire: &[Some(0), Some(1)],
+    },
+    login: StateIds {
+        wire_to_internal: &[Some(0), Some(1)],
+        internal_to_wire: &[Some(0), Some(1)],
+    },
+    play: StateIds {
+        wire_to_internal: &[
+            Some(0), Some(1), Some(2), Some(3), Some(4), Some(5), Some(6), Some(7), Some(8),
+            Some(9), Some(10), Some(11), Some(12), Some(13), Some(14), Some(15), Some(16),
+            Some(17), Some(18), Some(19), Some(20), Some(21), Some(22), Some(23), Some(24),
+            Some(25), Some(26), Some(27), Some(28), Some(29), Some(30), Some(31), Some(32),
+        ],
+        internal_to_wire: &[
+            Some(0), Some(1), Some(2), Some(3), Some(4), Some(5), Some(6), Some(7), Some(8),
+            Some(9), Some(10), Some(11), Some(12), Some(13), Some(14), Some(15), Some(16),
+            Some(17), Some(18), Some(19), Some(20), Some(21), Some(22), Some(23), Some(24),
+            Some(25), Some(26), Some(27), Some(28), Some(29), Some(30), Some(31), Some(32),
+        ],
+    },
+};
+
+/// Protocol 401 (1.13.2). `CraftingBookData` (internal id 23) was folded
+/// into the recipe book rewrite and no longer has a wire id of its own in
+/// this version, so every Play packet after it shifts down by one on the
+/// wire.
+const TABLE_401: IdTable = IdTable {
+    protocol_version: 401,
+    handshake: StateIds {
+        wire_to_internal: &[Some(0)],
+        internal_to_wire: &[Some(0)],
+    },
+    status: StateIds {
+        wire_to_internal: &[Some(0), Some(1)],
+        internal_to_wire: &[Some(0), Some(1)],
+    },
+    login: StateIds {
+        wire_to_internal: &[Some(0), Some(1)],
+        internal_to_wire: &[Some(0), Some(1)],
+    },
+    play: StateIds {
+        // wire id -> internal id, skipping over the removed CraftingBookData
+        wire_to_internal: &[
+            Some(0), Some(1), Some(2), Some(3), Some(4), Some(5), Some(6), Some(7), Some(8),
+            Some(9), Some(10), Some(11), Some(12), Some(13), Some(14), Some(15), Some(16),
+            Some(17), Some(18), Some(19), Some(20), Some(21), Some(22), Some(24), Some(25),
+            Some(26), Some(27), Some(28), Some(29), Some(30), Some(31), Some(32),
+        ],
+        // internal id -> wire id; CraftingBookData (23) has none on this version
+        internal_to_wire: &[
+            Some(0), Some(1), Some(2), Some(3), Some(4), Some(5), Some(6), Some(7), Some(8),
+            Some(9), Some(10), Some(11), Some(12), Some(13), Some(14), Some(15), Some(16),
+            Some(17), Some(18), Some(19), Some(20), Some(21), Some(22), None, Some(23),
+            Some(24), Some(25), Some(26), Some(27), Some(28), Some(29), Some(30), Some(31),
+        ],
+    },
+};
+
+const TABLES: &[&IdTable] = &[&TABLE_340, &TABLE_401];
+
+fn table_for(protocol_version: i32) -> Result<&'static IdTable> {
+    TABLES
+        .iter()
+        .find(|table| table.protocol_version == protocol_version)
+        .map(|table| &**table)
+        .ok_or_else(|| {
+            format!(
+                "Unsupported protocol version {}, supported versions are {:?}",
+                protocol_version, SUPPORTED_PROTOCOLS
+            )
+            .into()
+        })
+}
+
+fn state_ids(table: &'static IdTable, state: &ClientState) -> &'static StateIds {
+    match state {
+        &ClientState::Handshake => &table.handshake,
+        &ClientState::Status => &table.status,
+        &ClientState::Login => &table.login,
+        &ClientState::Play => &table.play,
+    }
+}
+
+/// Look up the internal id for a wire id read off the socket in the given
+/// state and protocol version. Returns an error if either the protocol
+/// version is unsupported or the wire id doesn't exist in that version.
+pub fn internal_id(state: &ClientState, protocol_version: i32, wire_id: i32) -> Result<InternalId> {
+    let table = table_for(protocol_version)?;
+    let ids = state_ids(table, state);
+    match ids.wire_to_internal.get(wire_id as usize) {
+        Some(&Some(internal)) => Ok(internal),
+        _ => bail!(
+            "No packet with wire id {} in state {} for protocol {}",
+            wire_id,
+            state,
+            protocol_version
+        ),
+    }
+}
+
+/// Look up the wire id to send for an internal id in the given state and
+/// protocol version. Returns an error if the packet doesn't exist on the
+/// wire in that version.
+pub fn wire_id(state: &ClientState, protocol_version: i32, internal_id: InternalId) -> Result<i32> {
+    let table = table_for(protocol_version)?;
+    let ids = state_ids(table, state);
+    match ids.internal_to_wire.get(internal_id as usize) {
+        Some(&Some(wire)) => Ok(wire),
+        _ => bail!(
+            "Packet with internal id {} in state {} has no wire id in protocol {}",
+            internal_id,
+            state,
+            protocol_version
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_ids_round_trip_on_340() {
+        for internal in 0..33 {
+            let wire = wire_id(&ClientState::Play, 340, internal).unwrap();
+            assert_eq!(wire, internal);
+            assert_eq!(internal_id(&ClientState::Play, 340, wire).unwrap(), internal);
+        }
+    }
+
+    #[test]
+    fn crafting_book_data_has_no_wire_id_on_401() {
+        assert!(wire_id(&ClientState::Play, 401, 23).is_err());
+    }
+
+    #[test]
+    fn ids_after_the_removed_packet_shift_down_by_one_on_401() {
+        assert_eq!(wire_id(&ClientState::Play, 401, 24).unwrap(), 23);
+        assert_eq!(internal_id(&ClientState::Play, 401, 23).unwrap(), 24);
+    }
+
+    #[test]
+    fn unsupported_protocol_version_is_an_error() {
+        assert!(wire_id(&ClientState::Play, 9999, 0).is_err());
+        assert!(internal_id(&ClientState::Play, 9999, 0).is_err());
+    }
+}
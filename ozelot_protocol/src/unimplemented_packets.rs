@@ -0,0 +1,75 @@
+//! Placeholder packet types for the `ServerboundPacket` variants
+//! `packets.clj` hasn't generated real structs for yet. Before `nbt::Slot`
+//! existed every inventory field was an opaque buffer too (see `nbt.rs`'s
+//! doc comment); these packets are the same idea applied to packets this
+//! series didn't touch - each just carries its payload as raw bytes so
+//! `ServerboundPacket::deserialize`/`to_u8` have something real to call
+//! until the packet gets its own typed module.
+
+use errors::*;
+use std::io::{Read, Write};
+
+macro_rules! opaque_packets {
+    ($($name:ident),* $(,)*) => {
+        $(
+            /// Placeholder - not yet generated by `packets.clj`. Carries
+            /// its payload as an opaque buffer; replace with a typed
+            /// struct (see `keep_alive.rs`/`chat_message.rs` for the
+            /// pattern) when this packet's fields are needed.
+            #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+            pub struct $name {
+                pub payload: Vec<u8>,
+            }
+
+            impl $name {
+                pub fn deserialize<R: Read>(r: &mut R) -> Result<Self> {
+                    let mut payload = Vec::new();
+                    r.read_to_end(&mut payload)?;
+                    Ok($name { payload: payload })
+                }
+
+                pub fn to_u8(&self) -> Result<Vec<u8>> {
+                    let mut ret = Vec::new();
+                    ret.write_all(&self.payload)?;
+                    Ok(ret)
+                }
+            }
+        )*
+    };
+}
+
+opaque_packets!(
+    Handshake,
+    StatusRequest,
+    StatusPing,
+    LoginStart,
+    EncryptionResponse,
+    TeleportConfirm,
+    TabComplete,
+    ClientStatus,
+    ClientSettings,
+    ConfirmTransaction,
+    CloseWindow,
+    PluginMessage,
+    UseEntity,
+    Player,
+    PlayerPosition,
+    PlayerPositionAndLook,
+    PlayerLook,
+    VehicleMove,
+    SteerBoat,
+    CraftRecipeRequest,
+    PlayerAbilities,
+    PlayerDigging,
+    EntityAction,
+    SteerVehicle,
+    CraftingBookData,
+    ResourcePackStatus,
+    AdvancementTab,
+    HeldItemChange,
+    UpdateSign,
+    Animation,
+    Spectate,
+    PlayerBlockPlacement,
+    UseItem,
+);
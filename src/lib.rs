@@ -0,0 +1,7 @@
+//! The top-level `ozelot` crate. The packet format itself now lives in
+//! `ozelot_protocol` (see that crate's docs); this crate re-exports it and
+//! builds the `Client`/connection layer on top.
+
+extern crate ozelot_protocol;
+
+pub use ozelot_protocol::*;